@@ -1,15 +1,16 @@
-use std::collections::{BTreeMap, HashMap};
-use std::iter::FromIterator;
-use std::fs::{remove_dir_all, copy, remove_file};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{remove_dir_all, copy, remove_file, metadata};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use glob::glob;
+use rayon::prelude::*;
 use tera::{Tera, Context};
 use slug::slugify;
 use walkdir::WalkDir;
 
 use errors::{Result, ResultExt};
-use config::{Config, get_config};
+use config::{Config, Taxonomy, get_config};
 use page::{Page};
 use utils::{create_file, create_directory};
 use section::{Section};
@@ -21,19 +22,14 @@ lazy_static! {
         tera.add_raw_templates(vec![
             ("rss.xml", include_str!("templates/rss.xml")),
             ("sitemap.xml", include_str!("templates/sitemap.xml")),
+            ("split_sitemap_index.xml", include_str!("templates/split_sitemap_index.xml")),
         ]).unwrap();
         tera
     };
 }
 
 
-#[derive(Debug, PartialEq)]
-enum RenderList {
-    Tags,
-    Categories,
-}
-
-/// A tag or category
+/// A single term of a taxonomy (e.g. one tag or one category)
 #[derive(Debug, Serialize, PartialEq)]
 struct ListItem {
     name: String,
@@ -51,6 +47,72 @@ impl ListItem {
     }
 }
 
+/// The maximum number of URLs a single `sitemap.xml` may hold per the
+/// sitemaps protocol; past it we emit a sitemap index
+const SITEMAP_LIMIT: usize = 50_000;
+
+/// A single `<url>` entry of a sitemap
+#[derive(Debug, Serialize)]
+struct SitemapEntry {
+    permalink: String,
+    lastmod: Option<String>,
+    priority: Option<f32>,
+    changefreq: Option<String>,
+}
+
+impl SitemapEntry {
+    /// An entry with no crawler hints, used for generated list pages
+    fn new(permalink: String) -> SitemapEntry {
+        SitemapEntry {
+            permalink: permalink,
+            lastmod: None,
+            priority: None,
+            changefreq: None,
+        }
+    }
+
+    fn from_page(page: &Page) -> SitemapEntry {
+        SitemapEntry {
+            permalink: page.permalink.clone(),
+            lastmod: page.meta.date.clone(),
+            priority: page.meta.priority,
+            changefreq: page.meta.changefreq.clone(),
+        }
+    }
+
+    fn from_section(section: &Section) -> SitemapEntry {
+        SitemapEntry {
+            permalink: section.permalink.clone(),
+            lastmod: None,
+            priority: section.meta.priority,
+            changefreq: section.meta.changefreq.clone(),
+        }
+    }
+}
+
+/// The `mtime`/size of a static file, used to decide whether it needs to be
+/// re-copied on a rebuild
+#[derive(Debug, Clone, PartialEq)]
+struct StaticFileInfo {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// One page of a paginated list, handed to the template as `paginator`
+#[derive(Debug, Serialize)]
+struct Pager<'a> {
+    /// 1-indexed number of the current page
+    current_index: usize,
+    /// Total number of pages the list was split into
+    number_of_pages: usize,
+    /// Permalink of the previous page, if any
+    previous: Option<String>,
+    /// Permalink of the next page, if any
+    next: Option<String>,
+    /// The items displayed on the current page
+    pages: Vec<&'a Page>,
+}
+
 #[derive(Debug)]
 pub struct Site {
     pub base_path: PathBuf,
@@ -60,8 +122,11 @@ pub struct Site {
     pub templates: Tera,
     live_reload: bool,
     output_path: PathBuf,
-    pub tags: HashMap<String, Vec<PathBuf>>,
-    pub categories: HashMap<String, Vec<PathBuf>>,
+    /// taxonomy name -> term -> pages tagged with that term
+    pub taxonomies: HashMap<String, HashMap<String, Vec<PathBuf>>>,
+    /// Relative path (inside `static`) -> last copied mtime/size, so rebuilds
+    /// only re-copy files that actually changed
+    static_manifest: HashMap<PathBuf, StaticFileInfo>,
 }
 
 impl Site {
@@ -82,8 +147,8 @@ impl Site {
             templates: tera,
             live_reload: false,
             output_path: PathBuf::from("public"),
-            tags: HashMap::new(),
-            categories: HashMap::new(),
+            taxonomies: HashMap::new(),
+            static_manifest: HashMap::new(),
         };
 
         Ok(site)
@@ -147,23 +212,25 @@ impl Site {
         Ok(())
     }
 
-    /// Separated from `parse` for easier testing
+    /// Separated from `parse` for easier testing.
+    /// Reads the terms of every taxonomy declared in the config out of each
+    /// page's front matter into `self.taxonomies`.
     pub fn parse_tags_and_categories(&mut self) {
-        for page in self.pages.values() {
-            if let Some(ref category) = page.meta.category {
-                self.categories
-                    .entry(category.to_string())
-                    .or_insert_with(|| vec![])
-                    .push(page.file_path.clone());
+        for taxonomy in &self.config.taxonomies {
+            let mut terms: HashMap<String, Vec<PathBuf>> = HashMap::new();
+            for page in self.pages.values() {
+                if let Some(values) = page.meta.taxonomies.get(&taxonomy.name) {
+                    for value in values {
+                        terms
+                            .entry(value.to_string())
+                            .or_insert_with(|| vec![])
+                            .push(page.file_path.clone());
+                    }
+                }
             }
 
-            if let Some(ref tags) = page.meta.tags {
-                for tag in tags {
-                    self.tags
-                        .entry(tag.to_string())
-                        .or_insert_with(|| vec![])
-                        .push(page.file_path.clone());
-                }
+            if !terms.is_empty() {
+                self.taxonomies.insert(taxonomy.name.clone(), terms);
             }
         }
     }
@@ -180,54 +247,118 @@ impl Site {
         html
     }
 
-    /// Copy the content of the `static` folder into the `public` folder
+    /// Copy the content of the `static` folder into the `public` folder.
     ///
-    /// TODO: only copy one file if possible because that would be a waste
-    /// to do re-copy the whole thing. Benchmark first to see if it's a big difference
-    pub fn copy_static_directory(&self) -> Result<()> {
+    /// Only files whose source is newer (different mtime or size) than the
+    /// last copy are re-copied, and targets whose source has disappeared are
+    /// deleted, so watch-mode rebuilds stay cheap for large asset folders.
+    pub fn copy_static_directory(&mut self) -> Result<()> {
         let from = Path::new("static");
         let target = Path::new("public");
 
+        // Relative paths we saw this pass, to detect deletions afterwards
+        let mut seen = HashSet::new();
+
         for entry in WalkDir::new(from).into_iter().filter_map(|e| e.ok()) {
-            let relative_path = entry.path().strip_prefix(&from).unwrap();
-            let target_path = {
-                let mut target_path = target.to_path_buf();
-                target_path.push(relative_path);
-                target_path
-            };
+            let relative_path = entry.path().strip_prefix(&from).unwrap().to_path_buf();
+            let target_path = target.join(&relative_path);
 
             if entry.path().is_dir() {
                 if !target_path.exists() {
                     create_directory(&target_path)?;
                 }
-            } else {
+                continue;
+            }
+
+            seen.insert(relative_path.clone());
+
+            let meta = metadata(entry.path())?;
+            let info = StaticFileInfo {
+                size: meta.len(),
+                modified: meta.modified().ok(),
+            };
+
+            // Skip files that are already up to date in `public`
+            let up_to_date = target_path.exists()
+                && self.static_manifest.get(&relative_path).map_or(false, |prev| *prev == info);
+            if !up_to_date {
                 if target_path.exists() {
                     remove_file(&target_path)?;
                 }
                 copy(entry.path(), &target_path)?;
             }
+
+            self.static_manifest.insert(relative_path, info);
         }
+
+        // Delete targets whose source has been removed since the last copy
+        let removed: Vec<PathBuf> = self.static_manifest
+            .keys()
+            .filter(|path| !seen.contains(*path))
+            .cloned()
+            .collect();
+        for relative_path in removed {
+            let target_path = target.join(&relative_path);
+            if target_path.exists() {
+                remove_file(&target_path)?;
+            }
+            self.static_manifest.remove(&relative_path);
+        }
+
         Ok(())
     }
 
     /// Deletes the `public` directory if it exists
-    pub fn clean(&self) -> Result<()> {
+    pub fn clean(&mut self) -> Result<()> {
         if Path::new("public").exists() {
             // Delete current `public` directory so we can start fresh
             remove_dir_all("public").chain_err(|| "Couldn't delete `public` directory")?;
         }
+        // The static manifest tracks what is in `public`, so wiping the
+        // directory means the manifest is stale
+        self.static_manifest.clear();
 
         Ok(())
     }
 
     pub fn rebuild_after_content_change(&mut self) -> Result<()> {
         self.parse()?;
-        self.build()
+        self.render_content()
     }
 
     pub fn rebuild_after_template_change(&mut self) -> Result<()> {
         self.templates.full_reload()?;
-        self.build_pages()
+        self.build_pages()?;
+        self.copy_static_directory()
+    }
+
+    /// The output directory of a page, mirroring the nesting of its URL.
+    fn page_output_path(&self, page: &Page) -> PathBuf {
+        let mut current_path = self.output_path.to_path_buf();
+        for component in page.url.split('/') {
+            current_path.push(component);
+        }
+        current_path
+    }
+
+    /// Renders a single page to its `index.html` and copies its assets.
+    /// Called from a worker thread by `build_pages`; the output directory is
+    /// created up-front and sequentially by `build_pages` so parallel workers
+    /// never race on directory creation.
+    fn render_page(&self, page: &Page) -> Result<()> {
+        let current_path = self.page_output_path(page);
+
+        // Finally, create a index.html file there with the page rendered
+        let output = page.render_html(&self.templates, &self.config)?;
+        create_file(current_path.join("index.html"), &self.inject_livereload(output))?;
+
+        // Copy any asset we found previously into the same directory as the index.html
+        for asset in &page.assets {
+            let asset_path = asset.as_path();
+            copy(&asset_path, &current_path.join(asset_path.file_name().unwrap()))?;
+        }
+
+        Ok(())
     }
 
     pub fn build_pages(&self) -> Result<()> {
@@ -236,47 +367,35 @@ impl Site {
             create_directory(&public)?;
         }
 
-        let mut pages = vec![];
-
-        // First we render the pages themselves
+        // Create every page's output directory sequentially first: pages that
+        // share a parent directory would otherwise race on the
+        // check-then-create below once we render in parallel.
         for page in self.pages.values() {
-            // Copy the nesting of the content directory if we have sections for that page
             let mut current_path = public.to_path_buf();
-
             for component in page.url.split('/') {
                 current_path.push(component);
-
                 if !current_path.exists() {
                     create_directory(&current_path)?;
                 }
             }
-
-            // Make sure the folder exists
-            create_directory(&current_path)?;
-
-            // Finally, create a index.html file there with the page rendered
-            let output = page.render_html(&self.templates, &self.config)?;
-            create_file(current_path.join("index.html"), &self.inject_livereload(output))?;
-
-            // Copy any asset we found previously into the same directory as the index.html
-            for asset in &page.assets {
-                let asset_path = asset.as_path();
-                copy(&asset_path, &current_path.join(asset_path.file_name().unwrap()))?;
-            }
-
-            pages.push(page);
         }
 
-        // Outputting categories and pages
-        if self.config.generate_categories_pages.unwrap() {
-            self.render_categories_and_tags(RenderList::Categories)?;
-        }
-        if self.config.generate_tags_pages.unwrap() {
-            self.render_categories_and_tags(RenderList::Tags)?;
-        }
+        // Then render the pages themselves, in parallel. `Tera::render` and
+        // `Config` are read-only during rendering and every page now has its
+        // own pre-created directory, so each page renders and copies its
+        // assets on its own worker thread; if any page fails, one of the
+        // errors (not necessarily the first) is returned here.
+        self.pages
+            .par_iter()
+            .map(|(_, page)| self.render_page(page))
+            .collect::<Result<()>>()?;
+
+        // Outputting every declared taxonomy (tags, categories, authors, …)
+        self.render_taxonomies()?;
 
         // And finally the index page
         let mut context = Context::new();
+        let mut pages = self.pages.values().collect::<Vec<&Page>>();
         pages.sort_by(|a, b| a.partial_cmp(b).unwrap());
         context.add("pages", &pages);
         context.add("config", &self.config);
@@ -287,8 +406,15 @@ impl Site {
     }
 
     /// Builds the site to the `public` directory after deleting it
-    pub fn build(&self) -> Result<()> {
+    pub fn build(&mut self) -> Result<()> {
         self.clean()?;
+        self.render_content()
+    }
+
+    /// Renders everything that depends on the parsed content into `public`.
+    /// Shared by the initial `build` and `rebuild_after_content_change` so they
+    /// stay in sync.
+    fn render_content(&mut self) -> Result<()> {
         self.build_pages()?;
         self.render_sitemap()?;
 
@@ -300,65 +426,158 @@ impl Site {
         self.copy_static_directory()
     }
 
-    /// Render the /{categories, list} pages and each individual category/tag page
-    /// They are the same thing fundamentally, a list of pages with something in common
-    fn render_categories_and_tags(&self, kind: RenderList) -> Result<()> {
-        let items = match kind {
-            RenderList::Categories => &self.categories,
-            RenderList::Tags => &self.tags,
+    /// Permalink of the `index`-th page of a paginated list rooted at
+    /// `permalink`. The first page keeps the root permalink, the others live
+    /// under `page/{index}/`.
+    fn paginate_permalink(permalink: &str, index: usize) -> String {
+        if index == 1 {
+            permalink.to_string()
+        } else {
+            format!("{}page/{}/", permalink, index)
+        }
+    }
+
+    /// Splits `pages` into `paginate_by`-sized chunks and renders each chunk
+    /// with `template`, writing the first page to `base_path/index.html` and
+    /// the others to `base_path/page/{n}/index.html`. `make_context` builds a
+    /// fresh base context for each page, to which a `paginator` is added.
+    fn render_paginated<F>(
+        &self,
+        base_path: &Path,
+        permalink: &str,
+        template: &str,
+        pages: &[&Page],
+        paginate_by: usize,
+        make_context: F,
+    ) -> Result<()>
+    where
+        F: Fn() -> Context,
+    {
+        // Always render at least one page, even with no items, so a section
+        // or term with only subsections still gets its landing `index.html`
+        let chunks: Vec<&[&Page]> = if pages.is_empty() {
+            vec![&[]]
+        } else {
+            pages.chunks(paginate_by).collect()
         };
+        let number_of_pages = chunks.len();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let current_index = i + 1;
+            let pager = Pager {
+                current_index,
+                number_of_pages,
+                previous: if current_index > 1 {
+                    Some(Site::paginate_permalink(permalink, current_index - 1))
+                } else {
+                    None
+                },
+                next: if current_index < number_of_pages {
+                    Some(Site::paginate_permalink(permalink, current_index + 1))
+                } else {
+                    None
+                },
+                pages: chunk.to_vec(),
+            };
 
-        if items.is_empty() {
+            let mut context = make_context();
+            context.add("paginator", &pager);
+            let output = self.templates.render(template, &context)?;
+
+            let page_path = if current_index == 1 {
+                base_path.to_path_buf()
+            } else {
+                let pages_dir = base_path.join("page");
+                if !pages_dir.exists() {
+                    create_directory(&pages_dir)?;
+                }
+                let page_path = pages_dir.join(current_index.to_string());
+                create_directory(&page_path)?;
+                page_path
+            };
+            create_file(page_path.join("index.html"), &self.inject_livereload(output))?;
+        }
+
+        Ok(())
+    }
+
+    /// Render every declared taxonomy.
+    fn render_taxonomies(&self) -> Result<()> {
+        for taxonomy in &self.config.taxonomies {
+            if let Some(terms) = self.taxonomies.get(&taxonomy.name) {
+                self.render_taxonomy(taxonomy, terms)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render the `{taxonomy}/index.html` list page and each individual
+    /// `{taxonomy}/{slug}/index.html` term page.
+    /// A taxonomy is fundamentally a list of pages grouped by something they
+    /// have in common, so every taxonomy is rendered the same way using the
+    /// `{taxonomy}/list.html` and `{taxonomy}/single.html` templates.
+    fn render_taxonomy(&self, taxonomy: &Taxonomy, terms: &HashMap<String, Vec<PathBuf>>) -> Result<()> {
+        if terms.is_empty() {
             return Ok(());
         }
 
-        let (list_tpl_name, single_tpl_name, name, var_name) = if kind == RenderList::Categories {
-            ("categories.html", "category.html", "categories", "category")
-        } else {
-            ("tags.html", "tag.html", "tags", "tag")
-        };
+        let name = &taxonomy.name;
 
-        // Create the categories/tags directory first
+        // Create the taxonomy directory first
         let public = self.output_path.clone();
         let mut output_path = public.to_path_buf();
         output_path.push(name);
         create_directory(&output_path)?;
 
-        // Then render the index page for that kind.
-        // We sort by number of page in that category/tag
+        // Then render the index page listing every term.
+        // We sort by the number of pages holding that term.
         let mut sorted_items = vec![];
-        for (item, count) in Vec::from_iter(items).into_iter().map(|(a, b)| (a, b.len())) {
-            sorted_items.push(ListItem::new(&item, count));
+        for (item, pages) in terms {
+            sorted_items.push(ListItem::new(item, pages.len()));
         }
         sorted_items.sort_by(|a, b| b.count.cmp(&a.count));
         let mut context = Context::new();
         context.add(name, &sorted_items);
         context.add("config", &self.config);
-        // And render it immediately
-        let list_output = self.templates.render(list_tpl_name, &context)?;
+        let list_output = self.templates.render(&format!("{}/list.html", name), &context)?;
         create_file(output_path.join("index.html"), &self.inject_livereload(list_output))?;
 
-        // Now, each individual item
-        for (item_name, pages_paths) in items.iter() {
-            let mut pages: Vec<&Page> = self.pages
+        // Now, each individual term
+        for (item_name, pages_paths) in terms.iter() {
+            let mut pages: Vec<&Page> = pages_paths
                 .iter()
-                .filter(|&(path, _)| pages_paths.contains(&path))
-                .map(|(_, page)| page)
+                .filter_map(|path| self.pages.get(path))
                 .collect();
             pages.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-            let mut context = Context::new();
             let slug = slugify(&item_name);
-            context.add(var_name, &item_name);
-            context.add(&format!("{}_slug", var_name), &slug);
-            context.add("pages", &pages);
-            context.add("config", &self.config);
-            let single_output = self.templates.render(single_tpl_name, &context)?;
-
-            create_directory(&output_path.join(&slug))?;
-            create_file(
-                output_path.join(&slug).join("index.html"),
-                &self.inject_livereload(single_output)
+            let term_dir = output_path.join(&slug);
+            create_directory(&term_dir)?;
+
+            let template = format!("{}/single.html", name);
+            let permalink = self.config.make_permalink(&format!("{}/{}", name, slug));
+
+            // Always go through the paginator so the `{name}/single.html`
+            // template has a single context shape: when `paginate_by` is unset
+            // the term renders as one page holding all its items.
+            let paginate_by = match taxonomy.paginate_by {
+                Some(paginate_by) if paginate_by > 0 => paginate_by,
+                _ => pages.len().max(1),
+            };
+            self.render_paginated(
+                &term_dir,
+                &permalink,
+                &template,
+                &pages,
+                paginate_by,
+                || {
+                    let mut context = Context::new();
+                    context.add("term", &item_name);
+                    context.add("term_slug", &slug);
+                    context.add("config", &self.config);
+                    context
+                },
             )?;
         }
 
@@ -366,48 +585,105 @@ impl Site {
     }
 
     fn render_sitemap(&self) -> Result<()> {
-        let mut context = Context::new();
-        context.add("pages", &self.pages.values().collect::<Vec<&Page>>());
-        context.add("sections", &self.sections.values().collect::<Vec<&Section>>());
-
-        let mut categories = vec![];
-        if self.config.generate_categories_pages.unwrap() {
-            if !self.categories.is_empty() {
-                categories.push(self.config.make_permalink("categories"));
-                for category in self.categories.keys() {
-                    categories.push(
-                        self.config.make_permalink(&format!("categories/{}", slugify(category)))
-                    );
-                }
+        // Gather every URL with its crawler hints first, so we know whether we
+        // need to split into several sitemaps
+        let mut entries = vec![];
+        for page in self.pages.values() {
+            entries.push(SitemapEntry::from_page(page));
+        }
+        for section in self.sections.values() {
+            entries.push(SitemapEntry::from_section(section));
+        }
+        for (name, terms) in &self.taxonomies {
+            entries.push(SitemapEntry::new(self.config.make_permalink(name)));
+            for term in terms.keys() {
+                entries.push(SitemapEntry::new(
+                    self.config.make_permalink(&format!("{}/{}", name, slugify(term)))
+                ));
             }
         }
-        context.add("categories", &categories);
 
-        let mut tags = vec![];
-        if self.config.generate_tags_pages.unwrap() {
-            if !self.tags.is_empty() {
-                tags.push(self.config.make_permalink("tags"));
-                for tag in self.tags.keys() {
-                    tags.push(
-                        self.config.make_permalink(&format!("tags/{}", slugify(tag)))
-                    );
-                }
-            }
+        // The common case: everything fits in a single sitemap
+        if entries.len() <= SITEMAP_LIMIT {
+            let mut context = Context::new();
+            context.add("entries", &entries);
+            let sitemap = self.templates.render("sitemap.xml", &context)?;
+            create_file(self.output_path.join("sitemap.xml"), &sitemap)?;
+            return Ok(());
         }
-        context.add("tags", &tags);
 
-        let sitemap = self.templates.render("sitemap.xml", &context)?;
+        // Too many URLs for one file: emit `sitemap-{n}.xml` chunks and a
+        // `sitemap.xml` index pointing at them
+        let mut sitemaps = vec![];
+        for (i, chunk) in entries.chunks(SITEMAP_LIMIT).enumerate() {
+            let name = format!("sitemap-{}.xml", i + 1);
+            let mut context = Context::new();
+            context.add("entries", &chunk.to_vec());
+            let sitemap = self.templates.render("sitemap.xml", &context)?;
+            create_file(self.output_path.join(&name), &sitemap)?;
+            sitemaps.push(self.config.make_permalink(&name));
+        }
 
-        create_file(self.output_path.join("sitemap.xml"), &sitemap)?;
+        let mut context = Context::new();
+        context.add("sitemaps", &sitemaps);
+        let index = self.templates.render("split_sitemap_index.xml", &context)?;
+        create_file(self.output_path.join("sitemap.xml"), &index)?;
 
         Ok(())
     }
 
+    /// Render the site-wide feed plus a feed for each section whose front
+    /// matter sets `generate_rss` and for each term of every taxonomy whose
+    /// config sets `rss`.
     fn render_rss_feed(&self) -> Result<()> {
-        let mut context = Context::new();
-        let mut pages = self.pages.values()
+        // The site-wide feed, built from every page
+        self.render_rss_feed_for(self.pages.values().collect(), Path::new("rss.xml"))?;
+
+        // One feed per section that opted in
+        for section in self.sections.values() {
+            if !section.meta.generate_rss.unwrap_or(false) {
+                continue;
+            }
+            // The root section has no components and would write to `rss.xml`,
+            // clobbering the site-wide feed above; it's already covered by it
+            if section.components.is_empty() {
+                continue;
+            }
+            let mut path = PathBuf::new();
+            for component in &section.components {
+                path.push(component);
+            }
+            path.push("rss.xml");
+            self.render_rss_feed_for(section.pages.iter().collect(), &path)?;
+        }
+
+        // One feed per term of every taxonomy that opted in
+        for taxonomy in &self.config.taxonomies {
+            if !taxonomy.rss {
+                continue;
+            }
+            if let Some(terms) = self.taxonomies.get(&taxonomy.name) {
+                for (term, pages_paths) in terms {
+                    let pages: Vec<&Page> = pages_paths
+                        .iter()
+                        .filter_map(|path| self.pages.get(path))
+                        .collect();
+                    let path = PathBuf::from(&taxonomy.name)
+                        .join(slugify(term))
+                        .join("rss.xml");
+                    self.render_rss_feed_for(pages, &path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render an RSS feed from `pages` to `path`, relative to the output
+    /// directory. Does nothing if none of the pages has a date.
+    fn render_rss_feed_for(&self, pages: Vec<&Page>, path: &Path) -> Result<()> {
+        let mut pages = pages.into_iter()
             .filter(|p| p.meta.date.is_some())
-            .take(15) // limit to the last 15 elements
             .collect::<Vec<&Page>>();
 
         // Don't generate a RSS feed if none of the pages has a date
@@ -415,21 +691,38 @@ impl Site {
             return Ok(());
         }
 
+        // Sort first, then keep only the 15 newest pages
         pages.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        pages.truncate(15);
+
+        let mut context = Context::new();
         context.add("pages", &pages);
         context.add("last_build_date", &pages[0].meta.date);
         context.add("config", &self.config);
 
+        // Join the components with `/` so feed URLs stay forward-slashed on
+        // every platform, rather than stringifying the `PathBuf` directly
+        let url_path = path.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
         let rss_feed_url = if self.config.base_url.ends_with('/') {
-            format!("{}{}", self.config.base_url, "feed.xml")
+            format!("{}{}", self.config.base_url, url_path)
         } else {
-            format!("{}/{}", self.config.base_url, "feed.xml")
+            format!("{}/{}", self.config.base_url, url_path)
         };
         context.add("feed_url", &rss_feed_url);
 
-        let sitemap = self.templates.render("rss.xml", &context)?;
+        let feed = self.templates.render("rss.xml", &context)?;
 
-        create_file(self.output_path.join("rss.xml"), &sitemap)?;
+        // Make sure the containing directory exists before writing the feed
+        let output = self.output_path.join(path);
+        if let Some(parent) = output.parent() {
+            if !parent.exists() {
+                create_directory(parent)?;
+            }
+        }
+        create_file(output, &feed)?;
 
         Ok(())
     }
@@ -437,19 +730,62 @@ impl Site {
     fn render_sections(&self) -> Result<()> {
         let public = self.output_path.clone();
 
+        // Create every section's output directory sequentially first, for the
+        // same reason as `build_pages`: nested sections share parent
+        // directories and would race on the check-then-create once rendered
+        // in parallel.
         for section in self.sections.values() {
             let mut output_path = public.to_path_buf();
             for component in &section.components {
                 output_path.push(component);
-
                 if !output_path.exists() {
                     create_directory(&output_path)?;
                 }
             }
+        }
 
-            let output = section.render_html(&self.templates, &self.config)?;
-            create_file(output_path.join("index.html"), &self.inject_livereload(output))?;
+        self.sections
+            .par_iter()
+            .map(|(_, section)| self.render_section(section))
+            .collect::<Result<()>>()
+    }
+
+    /// The output directory of a section, mirroring the nesting of its URL.
+    fn section_output_path(&self, section: &Section) -> PathBuf {
+        let mut output_path = self.output_path.to_path_buf();
+        for component in &section.components {
+            output_path.push(component);
         }
+        output_path
+    }
+
+    /// Renders a single section to its `index.html`. Called from a worker
+    /// thread by `render_sections`; the output directory is pre-created
+    /// sequentially by `render_sections`.
+    fn render_section(&self, section: &Section) -> Result<()> {
+        let output_path = self.section_output_path(section);
+
+        let mut pages = section.pages.iter().collect::<Vec<&Page>>();
+        pages.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // Always render through the paginator so a paginated section sees the
+        // exact same context as an unpaginated one — `Section::build_context`
+        // is what `render_html` uses internally (including `current_url`/
+        // `current_path`), plus the added `paginator`. When `paginate_by` is
+        // unset the section renders as a single page holding every item.
+        let template = section.get_template_name();
+        let paginate_by = match section.meta.paginate_by {
+            Some(paginate_by) if paginate_by > 0 => paginate_by,
+            _ => pages.len().max(1),
+        };
+        self.render_paginated(
+            &output_path,
+            &section.permalink,
+            &template,
+            &pages,
+            paginate_by,
+            || section.build_context(&self.config),
+        )?;
 
         Ok(())
     }